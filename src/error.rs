@@ -21,6 +21,10 @@ pub enum JsonError {
   NumberRange(Option<Location>),
   /// Errors caused by bad Unicode
   Unicode(Option<Location>),
+  /// Errors caused by input that is not valid UTF-8
+  Utf8(Option<Location>),
+  /// Errors caused by a failure to read from an IO source
+  Io(String),
 }
 
 impl From<pest::error::Error<Rule>> for JsonError {
@@ -40,6 +44,8 @@ impl Display for JsonError {
       JsonError::NumberFormat(_) => write!(formatter, "bad number format"),
       JsonError::NumberRange(_) => write!(formatter, "bad number range"),
       JsonError::Unicode(_) => write!(formatter, "bad Unicode characters"),
+      JsonError::Utf8(_) => write!(formatter, "invalid UTF-8 input"),
+      JsonError::Io(ref msg) => write!(formatter, "{}", msg),
     }
   }
 }
@@ -0,0 +1,201 @@
+use crate::JsonNode;
+
+/// Indentation style used by [`crate::stringify_pretty`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Indent {
+  /// Indent with the given number of spaces per level
+  Spaces(usize),
+  /// Indent with one tab character per level
+  Tabs,
+}
+
+impl Indent {
+  fn render(&self, level: usize) -> String {
+    match self {
+      Indent::Spaces(n) => " ".repeat(n * level),
+      Indent::Tabs => "\t".repeat(level),
+    }
+  }
+}
+
+/// How strings should be quoted by [`crate::stringify_pretty`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuoteStyle {
+  /// Always use double quotes, e.g. `"name"`
+  Double,
+  /// Always use single quotes, e.g. `'name'`
+  Single,
+}
+
+impl QuoteStyle {
+  fn quote(&self) -> char {
+    match self {
+      QuoteStyle::Double => '"',
+      QuoteStyle::Single => '\'',
+    }
+  }
+}
+
+/// Options controlling the output of [`crate::stringify_pretty`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatOptions {
+  /// The indentation used for each nesting level
+  pub indent: Indent,
+  /// Whether to emit a space after the `:` in an object entry
+  pub space_after_colon: bool,
+  /// Whether to emit a trailing comma after the last entry in an object or array
+  pub trailing_commas: bool,
+  /// The quote character used for strings and quoted keys
+  pub quote_style: QuoteStyle,
+}
+
+impl Default for FormatOptions {
+  fn default() -> Self {
+    Self {
+      indent: Indent::Spaces(2),
+      space_after_colon: true,
+      trailing_commas: false,
+      quote_style: QuoteStyle::Double,
+    }
+  }
+}
+
+/// Pretty-print a node tree into a human-readable, multi-line JSON5 format,
+/// with one entry per line and cumulative indentation, matching the style
+/// of `serde_json`'s `to_string_pretty`.
+pub fn stringify_pretty(node: &JsonNode, opts: &FormatOptions) -> String {
+  let mut result = String::new();
+
+  write_node(node, opts, 0, &mut result);
+  result
+}
+
+fn write_node(node: &JsonNode, opts: &FormatOptions, level: usize, out: &mut String) {
+  use JsonNode::*;
+
+  match node {
+    Object(o, _) => {
+      if o.is_empty() {
+        out.push_str("{}");
+        return;
+      }
+
+      out.push('{');
+      out.push('\n');
+
+      let count = o.len();
+
+      for (i, (name, value)) in o.iter().enumerate() {
+        out.push_str(&opts.indent.render(level + 1));
+        write_key(name, opts, out);
+        out.push(':');
+
+        if opts.space_after_colon {
+          out.push(' ');
+        }
+
+        write_node(value, opts, level + 1, out);
+
+        if i + 1 < count || opts.trailing_commas {
+          out.push(',');
+        }
+
+        out.push('\n');
+      }
+
+      out.push_str(&opts.indent.render(level));
+      out.push('}');
+    }
+    Array(a, _) => {
+      if a.is_empty() {
+        out.push_str("[]");
+        return;
+      }
+
+      out.push('[');
+      out.push('\n');
+
+      for (i, value) in a.iter().enumerate() {
+        out.push_str(&opts.indent.render(level + 1));
+        write_node(value, opts, level + 1, out);
+
+        if i + 1 < a.len() || opts.trailing_commas {
+          out.push(',');
+        }
+
+        out.push('\n');
+      }
+
+      out.push_str(&opts.indent.render(level));
+      out.push(']');
+    }
+    String(s, _) => {
+      let quote = opts.quote_style.quote();
+      out.push(quote);
+      out.push_str(s);
+      out.push(quote);
+    }
+    Integer(i, _) => out.push_str(&i.to_string()),
+    Float(f, _) => out.push_str(&f.to_string()),
+    Number(s, _) => out.push_str(s),
+    Bool(b, _) => out.push_str(&b.to_string()),
+    Null(_) => out.push_str("null"),
+  }
+}
+
+fn write_key(name: &str, opts: &FormatOptions, out: &mut String) {
+  if name.contains(char::is_whitespace) {
+    let quote = opts.quote_style.quote();
+    out.push(quote);
+    out.push_str(name);
+    out.push(quote);
+  } else {
+    out.push_str(name);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::parse;
+
+  #[test]
+  fn test_stringify_pretty_object() {
+    let tree = parse("{a: 1, b: [1, 2]}").unwrap();
+
+    assert_eq!(
+      stringify_pretty(&tree, &FormatOptions::default()),
+      "{\n  a: 1,\n  b: [\n    1,\n    2\n  ]\n}"
+    );
+  }
+
+  #[test]
+  fn test_stringify_pretty_empty() {
+    let tree = parse("{}").unwrap();
+
+    assert_eq!(stringify_pretty(&tree, &FormatOptions::default()), "{}");
+  }
+
+  #[test]
+  fn test_stringify_pretty_tabs_and_trailing_commas() {
+    let tree = parse("{a: 1}").unwrap();
+    let opts = FormatOptions {
+      indent: Indent::Tabs,
+      trailing_commas: true,
+      ..FormatOptions::default()
+    };
+
+    assert_eq!(stringify_pretty(&tree, &opts), "{\n\ta: 1,\n}");
+  }
+
+  #[test]
+  fn test_stringify_pretty_single_quotes() {
+    let tree = parse("{a: 'x'}").unwrap();
+    let opts = FormatOptions {
+      quote_style: QuoteStyle::Single,
+      ..FormatOptions::default()
+    };
+
+    assert_eq!(stringify_pretty(&tree, &opts), "{\n  a: 'x'\n}");
+  }
+}
@@ -0,0 +1,116 @@
+use crate::JsonNode;
+use hashlink::LinkedHashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Converts a native Rust value into a [`JsonNode`] tree with `None`
+/// locations, so output can be built programmatically and passed straight
+/// to [`crate::stringify`]/[`crate::stringify_pretty`].
+pub trait IntoJson {
+  fn into_json(self) -> JsonNode;
+}
+
+impl IntoJson for bool {
+  fn into_json(self) -> JsonNode {
+    JsonNode::Bool(self, None)
+  }
+}
+
+macro_rules! impl_into_json_integer {
+  ($($ty:ty),*) => {
+    $(
+      impl IntoJson for $ty {
+        fn into_json(self) -> JsonNode {
+          JsonNode::Integer(self as i64, None)
+        }
+      }
+    )*
+  };
+}
+
+impl_into_json_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl IntoJson for f64 {
+  fn into_json(self) -> JsonNode {
+    JsonNode::Float(self, None)
+  }
+}
+
+impl IntoJson for String {
+  fn into_json(self) -> JsonNode {
+    JsonNode::String(self, None)
+  }
+}
+
+impl IntoJson for &str {
+  fn into_json(self) -> JsonNode {
+    JsonNode::String(self.to_string(), None)
+  }
+}
+
+impl<T: IntoJson> IntoJson for Option<T> {
+  fn into_json(self) -> JsonNode {
+    match self {
+      Some(value) => value.into_json(),
+      None => JsonNode::Null(None),
+    }
+  }
+}
+
+impl<T: IntoJson> IntoJson for Vec<T> {
+  fn into_json(self) -> JsonNode {
+    JsonNode::Array(self.into_iter().map(IntoJson::into_json).collect(), None)
+  }
+}
+
+impl<T: IntoJson> IntoJson for BTreeMap<String, T> {
+  fn into_json(self) -> JsonNode {
+    let mut map = LinkedHashMap::new();
+
+    for (key, value) in self {
+      map.insert(key, value.into_json());
+    }
+
+    JsonNode::Object(map, None)
+  }
+}
+
+impl<T: IntoJson> IntoJson for HashMap<String, T> {
+  fn into_json(self) -> JsonNode {
+    let mut map = LinkedHashMap::new();
+
+    for (key, value) in self {
+      map.insert(key, value.into_json());
+    }
+
+    JsonNode::Object(map, None)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::stringify;
+
+  #[test]
+  fn test_into_json_primitives() {
+    assert_eq!(true.into_json(), JsonNode::Bool(true, None));
+    assert_eq!(1i32.into_json(), JsonNode::Integer(1, None));
+    assert_eq!(1.5f64.into_json(), JsonNode::Float(1.5, None));
+    assert_eq!("xyz".into_json(), JsonNode::String("xyz".to_string(), None));
+    assert_eq!(None::<i32>.into_json(), JsonNode::Null(None));
+  }
+
+  #[test]
+  fn test_into_json_vec() {
+    assert_eq!(stringify(&vec![1, 2, 3].into_json()), "[1,2,3]");
+  }
+
+  #[test]
+  fn test_into_json_map() {
+    let mut map = BTreeMap::new();
+    map.insert("a".to_string(), 1);
+    map.insert("b".to_string(), 2);
+
+    assert_eq!(stringify(&map.into_json()), "{a:1,b:2}");
+  }
+}
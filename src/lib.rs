@@ -1,7 +1,17 @@
 mod error;
+mod format;
+mod into_json;
+mod path;
+#[cfg(feature = "serde")]
+mod serde_impl;
 
 pub use error::{JsonError, Location};
+pub use format::{stringify_pretty, FormatOptions, Indent, QuoteStyle};
 pub use hashlink::linked_hash_map::{Iter, LinkedHashMap};
+pub use into_json::IntoJson;
+pub use path::{select, select_owned, CompareOp, Literal, Operand, Pred, PathStep};
+#[cfg(feature = "serde")]
+pub use serde_impl::{from_node, to_node};
 
 use pest::{iterators::Pair, Parser, Span};
 use pest_derive::Parser;
@@ -20,11 +30,56 @@ pub enum JsonNode {
   Bool(bool, Option<Location>),
   Integer(i64, Option<Location>),
   Float(f64, Option<Location>),
+  /// A number preserved verbatim as its original source text, produced when
+  /// parsing with [`ParseOptions::preserve_number_text`] set
+  Number(String, Option<Location>),
   String(String, Option<Location>),
   Array(Vec<JsonNode>, Option<Location>),
   Object(LinkedHashMap<String, JsonNode>, Option<Location>),
 }
 
+impl JsonNode {
+  /// If this is a [`JsonNode::Number`], parse its stored source text as an `i64`
+  pub fn as_i64(&self) -> Option<i64> {
+    match self {
+      JsonNode::Number(text, _) if is_hex_literal(text) => {
+        i64::from_str_radix(&text[2..], 16).ok()
+      }
+      JsonNode::Number(text, _) => text.parse::<i64>().ok(),
+      _ => None,
+    }
+  }
+
+  /// If this is a [`JsonNode::Number`], parse its stored source text as an `f64`
+  pub fn as_f64(&self) -> Option<f64> {
+    match self {
+      JsonNode::Number(text, _) => match text.as_str() {
+        "Infinity" => Some(f64::INFINITY),
+        "-Infinity" => Some(f64::NEG_INFINITY),
+        "NaN" | "-NaN" => Some(f64::NAN),
+        s => s.parse::<f64>().ok(),
+      },
+      _ => None,
+    }
+  }
+
+  /// If this is a [`JsonNode::Number`], return its stored source text
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      JsonNode::Number(text, _) => Some(text.as_str()),
+      _ => None,
+    }
+  }
+}
+
+/// Options controlling how [`parse_with`] interprets numeric tokens
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ParseOptions {
+  /// When true, numbers are kept as their exact source text in a
+  /// [`JsonNode::Number`] instead of being eagerly converted to `i64`/`f64`
+  pub preserve_number_text: bool,
+}
+
 /// JSON5 parser
 #[derive(Parser)]
 #[grammar = "json5.pest"]
@@ -32,17 +87,42 @@ struct Json5Parser;
 
 /// Parse a JSON5 string into [`JsonNode`]'s
 pub fn parse<'a>(input: &'a str) -> Result<JsonNode, JsonError> {
-  parse_pair(Json5Parser::parse(Rule::text, input)?.next().unwrap())
+  parse_with(input, &ParseOptions::default())
+}
+
+/// Parse a JSON5 string into [`JsonNode`]'s, using the given [`ParseOptions`]
+pub fn parse_with<'a>(input: &'a str, opts: &ParseOptions) -> Result<JsonNode, JsonError> {
+  parse_pair(Json5Parser::parse(Rule::text, input)?.next().unwrap(), opts)
+}
+
+/// Parse a JSON5 byte slice into [`JsonNode`]'s, validating that it is UTF-8 first
+pub fn from_slice(input: &[u8]) -> Result<JsonNode, JsonError> {
+  let text = std::str::from_utf8(input).map_err(|_| JsonError::Utf8(None))?;
+
+  parse(text)
 }
 
-fn parse_pair<'a>(pair: Pair<'a, Rule>) -> Result<JsonNode, JsonError> {
+/// Parse JSON5 from any [`std::io::Read`] source into [`JsonNode`]'s
+pub fn from_reader<R: std::io::Read>(mut reader: R) -> Result<JsonNode, JsonError> {
+  let mut buf = Vec::new();
+
+  reader
+    .read_to_end(&mut buf)
+    .map_err(|err| JsonError::Io(err.to_string()))?;
+
+  from_slice(&buf)
+}
+
+fn parse_pair<'a>(pair: Pair<'a, Rule>, opts: &ParseOptions) -> Result<JsonNode, JsonError> {
   let location = Some(Location::from(&pair.as_span()));
   let node: JsonNode = match pair.as_rule() {
     Rule::null => JsonNode::Null(location),
     Rule::boolean => JsonNode::Bool(pair.as_str() == "true", location),
     Rule::string | Rule::identifier => JsonNode::String(parse_string(pair)?, location),
     Rule::number => {
-      if is_int(pair.as_str()) {
+      if opts.preserve_number_text {
+        JsonNode::Number(pair.as_str().to_string(), location)
+      } else if is_int(pair.as_str()) {
         JsonNode::Integer(parse_integer(&pair)?, location)
       } else {
         JsonNode::Float(parse_float(&pair)?, location)
@@ -51,7 +131,7 @@ fn parse_pair<'a>(pair: Pair<'a, Rule>) -> Result<JsonNode, JsonError> {
     Rule::array => JsonNode::Array(
       pair
         .into_inner()
-        .map(parse_pair)
+        .map(|pair| parse_pair(pair, opts))
         .collect::<Result<Vec<_>, _>>()?,
       location,
     ),
@@ -61,7 +141,7 @@ fn parse_pair<'a>(pair: Pair<'a, Rule>) -> Result<JsonNode, JsonError> {
       for pair in pair.into_inner() {
         let mut key_value_pairs = pair.into_inner();
         let key = parse_string(key_value_pairs.next().unwrap())?;
-        let value = parse_pair(key_value_pairs.next().unwrap())?;
+        let value = parse_pair(key_value_pairs.next().unwrap(), opts)?;
 
         map.insert(key, value);
       }
@@ -248,6 +328,7 @@ pub fn stringify(node: &JsonNode) -> String {
     String(s, _) => format!("\"{}\"", s),
     Integer(i, _) => format!("{}", i),
     Float(f, _) => format!("{}", f),
+    Number(s, _) => s.clone(),
     Bool(b, _) => format!("{}", b),
     Null(_) => format!("null"),
   }
@@ -293,6 +374,27 @@ mod test {
     );
   }
 
+  #[test]
+  fn test_number_raw() {
+    let opts = ParseOptions {
+      preserve_number_text: true,
+    };
+
+    assert_eq!(
+      parse_with("1.10", &opts).unwrap(),
+      JsonNode::Number("1.10".to_string(), Some(Location { column: 1, line: 1 }))
+    );
+    assert_eq!(
+      stringify(&parse_with("1.10", &opts).unwrap()),
+      "1.10"
+    );
+
+    let node = parse_with("9223372036854775808", &opts).unwrap();
+    assert_eq!(node.as_i64(), None);
+    assert_eq!(node.as_f64(), Some(9223372036854775808.0));
+    assert_eq!(node.as_str(), Some("9223372036854775808"));
+  }
+
   #[test]
   fn test_string_escapes() {
     assert_eq!(
@@ -384,6 +486,31 @@ mod test {
     println!("{}", JsonError::NumberFormat(None));
     println!("{}", JsonError::NumberRange(None));
     println!("{}", JsonError::Unicode(None));
+    println!("{}", JsonError::Utf8(None));
+    println!("{}", JsonError::Io("broken pipe".to_string()));
+  }
+
+  #[test]
+  fn test_from_slice() {
+    assert_eq!(
+      from_slice(b"null").unwrap(),
+      JsonNode::Null(Some(Location { column: 1, line: 1 }))
+    );
+
+    match from_slice(&[0xff, 0xfe, 0xfd]) {
+      Err(JsonError::Utf8(None)) => (),
+      other => panic!("Unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_from_reader() {
+    let cursor = std::io::Cursor::new(b"true");
+
+    assert_eq!(
+      from_reader(cursor).unwrap(),
+      JsonNode::Bool(true, Some(Location { column: 1, line: 1 }))
+    );
   }
 
   #[test]
@@ -0,0 +1,715 @@
+use crate::{JsonError, JsonNode};
+
+/// One step of a parsed JSONPath expression, as produced by [`parse_path`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathStep {
+  /// `$`
+  Root,
+  /// `.name` or `['name']`
+  Child(String),
+  /// `.*` or `[*]`
+  Wildcard,
+  /// `..name`
+  Descendant(String),
+  /// `..[*]`
+  DescendantWildcard,
+  /// `[i]`, negative values count from the end of the array
+  Index(i64),
+  /// `[start:end:step]`, any field may be omitted
+  Slice {
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+  },
+  /// `['a','b']` or `[0,1]`
+  Union(Vec<PathStep>),
+  /// `[?(...)]`
+  Filter(Pred),
+}
+
+/// A filter predicate used by [`PathStep::Filter`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pred {
+  Compare(Operand, CompareOp, Literal),
+  And(Box<Pred>, Box<Pred>),
+  Or(Box<Pred>, Box<Pred>),
+}
+
+/// The left-hand side of a filter comparison: `@` or `@.field`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+  /// `@`, the value under test itself
+  This,
+  /// `@.field`, a field of the value under test
+  Field(String),
+}
+
+/// A comparison operator usable inside a filter predicate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CompareOp {
+  Eq,
+  Ne,
+  Lt,
+  Le,
+  Gt,
+  Ge,
+}
+
+/// A literal compared against in a filter predicate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+  Number(f64),
+  String(String),
+  Bool(bool),
+}
+
+/// Parse a JSONPath expression into a sequence of [`PathStep`]'s.
+pub fn parse_path(path: &str) -> Result<Vec<PathStep>, JsonError> {
+  Tokenizer::new(path).parse_steps()
+}
+
+/// Evaluate a JSONPath expression against a [`JsonNode`] tree, returning
+/// references to the matched nodes. Each step maps the nodes matched so far,
+/// in order, to their own matches, so the result is not always in document
+/// order: a union yields its members' matches in the order they are listed,
+/// and `..name` yields a node's own `name` child before the matches found
+/// deeper inside its other children.
+pub fn select<'a>(node: &'a JsonNode, path: &str) -> Result<Vec<&'a JsonNode>, JsonError> {
+  let steps = parse_path(path)?;
+  let mut working_set = vec![node];
+
+  for step in &steps {
+    working_set = apply_step(&working_set, step);
+  }
+
+  Ok(working_set)
+}
+
+/// Evaluate a JSONPath expression against a [`JsonNode`] tree, returning
+/// owned clones of the matched nodes in the same order as [`select`].
+pub fn select_owned(node: &JsonNode, path: &str) -> Result<Vec<JsonNode>, JsonError> {
+  Ok(select(node, path)?.into_iter().cloned().collect())
+}
+
+fn apply_step<'a>(working_set: &[&'a JsonNode], step: &PathStep) -> Vec<&'a JsonNode> {
+  match step {
+    PathStep::Root => working_set.to_vec(),
+    PathStep::Child(name) => working_set
+      .iter()
+      .filter_map(|node| child(node, name))
+      .collect(),
+    PathStep::Wildcard => working_set.iter().flat_map(|node| children(node)).collect(),
+    PathStep::Descendant(name) => {
+      let mut result = Vec::new();
+      let mut seen = Vec::new();
+
+      for node in working_set {
+        walk_descendants(node, &mut |candidate| {
+          if let Some(found) = child(candidate, name) {
+            if !seen.contains(&(found as *const JsonNode)) {
+              seen.push(found as *const JsonNode);
+              result.push(found);
+            }
+          }
+        });
+      }
+
+      result
+    }
+    PathStep::DescendantWildcard => {
+      let mut result = Vec::new();
+
+      for node in working_set {
+        walk_descendants(node, &mut |candidate| {
+          result.push(candidate);
+        });
+      }
+
+      result
+    }
+    PathStep::Index(i) => working_set
+      .iter()
+      .filter_map(|node| index(node, *i))
+      .collect(),
+    PathStep::Slice { start, end, step } => working_set
+      .iter()
+      .flat_map(|node| slice(node, *start, *end, *step))
+      .collect(),
+    PathStep::Union(steps) => steps.iter().flat_map(|step| apply_step(working_set, step)).collect(),
+    PathStep::Filter(pred) => working_set
+      .iter()
+      .flat_map(|node| filter_children(node, pred))
+      .collect(),
+  }
+}
+
+fn child<'a>(node: &'a JsonNode, name: &str) -> Option<&'a JsonNode> {
+  match node {
+    JsonNode::Object(map, _) => map.get(name),
+    _ => None,
+  }
+}
+
+fn children(node: &JsonNode) -> Vec<&JsonNode> {
+  match node {
+    JsonNode::Array(items, _) => items.iter().collect(),
+    JsonNode::Object(map, _) => map.iter().map(|(_, v)| v).collect(),
+    _ => Vec::new(),
+  }
+}
+
+fn walk_descendants<'a>(node: &'a JsonNode, visit: &mut impl FnMut(&'a JsonNode)) {
+  visit(node);
+
+  for node_child in children(node) {
+    walk_descendants(node_child, visit);
+  }
+}
+
+fn resolve_index(len: usize, i: i64) -> Option<usize> {
+  let resolved = if i < 0 { i + len as i64 } else { i };
+
+  if resolved < 0 || resolved as usize >= len {
+    None
+  } else {
+    Some(resolved as usize)
+  }
+}
+
+fn index(node: &JsonNode, i: i64) -> Option<&JsonNode> {
+  match node {
+    JsonNode::Array(items, _) => resolve_index(items.len(), i).map(|i| &items[i]),
+    _ => None,
+  }
+}
+
+fn slice(
+  node: &JsonNode,
+  start: Option<i64>,
+  end: Option<i64>,
+  step: Option<i64>,
+) -> Vec<&JsonNode> {
+  let items = match node {
+    JsonNode::Array(items, _) => items,
+    _ => return Vec::new(),
+  };
+  let len = items.len() as i64;
+  let step = step.unwrap_or(1);
+
+  if step == 0 || len == 0 {
+    return Vec::new();
+  }
+
+  if step > 0 {
+    let start = clamp_slice_bound(start.unwrap_or(0), len);
+    let end = clamp_slice_bound(end.unwrap_or(len), len);
+    let mut result = Vec::new();
+    let mut i = start;
+
+    while i < end {
+      if i >= 0 && i < len {
+        result.push(&items[i as usize]);
+      }
+      i += step;
+    }
+
+    result
+  } else {
+    let start = clamp_slice_bound(start.unwrap_or(len - 1), len);
+    // An omitted `end` means "run off the front of the array"; unlike an
+    // explicit `-1` it must not be resolved as a negative index (which
+    // would wrap it around to `len - 1` and make the loop below a no-op).
+    let end = match end {
+      Some(e) => clamp_slice_bound(e, len),
+      None => -1,
+    };
+    let mut result = Vec::new();
+    let mut i = start;
+
+    while i > end {
+      if i >= 0 && i < len {
+        result.push(&items[i as usize]);
+      }
+      i += step;
+    }
+
+    result
+  }
+}
+
+fn clamp_slice_bound(i: i64, len: i64) -> i64 {
+  let resolved = if i < 0 { i + len } else { i };
+  resolved.clamp(-1, len)
+}
+
+fn filter_children<'a>(node: &'a JsonNode, pred: &Pred) -> Vec<&'a JsonNode> {
+  children(node)
+    .into_iter()
+    .filter(|candidate| eval_pred(pred, candidate))
+    .collect()
+}
+
+fn eval_pred(pred: &Pred, node: &JsonNode) -> bool {
+  match pred {
+    Pred::Compare(operand, op, literal) => match resolve_operand(operand, node) {
+      Some(value) => compare(value, op, literal),
+      None => false,
+    },
+    Pred::And(lhs, rhs) => eval_pred(lhs, node) && eval_pred(rhs, node),
+    Pred::Or(lhs, rhs) => eval_pred(lhs, node) || eval_pred(rhs, node),
+  }
+}
+
+fn resolve_operand<'a>(operand: &Operand, node: &'a JsonNode) -> Option<&'a JsonNode> {
+  match operand {
+    Operand::This => Some(node),
+    Operand::Field(name) => child(node, name),
+  }
+}
+
+fn compare(node: &JsonNode, op: &CompareOp, literal: &Literal) -> bool {
+  use std::cmp::Ordering;
+
+  let ordering = match (node, literal) {
+    (JsonNode::Integer(i, _), Literal::Number(n)) => (*i as f64).partial_cmp(n),
+    (JsonNode::Float(f, _), Literal::Number(n)) => f.partial_cmp(n),
+    (JsonNode::Number(_, _), Literal::Number(n)) => node.as_f64().and_then(|f| f.partial_cmp(n)),
+    (JsonNode::String(s, _), Literal::String(t)) => Some(s.as_str().cmp(t.as_str())),
+    (JsonNode::Bool(b, _), Literal::Bool(t)) => Some(b.cmp(t)),
+    _ => None,
+  };
+
+  match (op, ordering) {
+    (CompareOp::Eq, Some(Ordering::Equal)) => true,
+    (CompareOp::Ne, Some(o)) => o != Ordering::Equal,
+    (CompareOp::Ne, None) => true,
+    (CompareOp::Lt, Some(Ordering::Less)) => true,
+    (CompareOp::Le, Some(Ordering::Less | Ordering::Equal)) => true,
+    (CompareOp::Gt, Some(Ordering::Greater)) => true,
+    (CompareOp::Ge, Some(Ordering::Greater | Ordering::Equal)) => true,
+    _ => false,
+  }
+}
+
+struct Tokenizer<'a> {
+  chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Tokenizer<'a> {
+  fn new(path: &'a str) -> Self {
+    Self {
+      chars: path.chars().peekable(),
+    }
+  }
+
+  fn parse_steps(mut self) -> Result<Vec<PathStep>, JsonError> {
+    let mut steps = Vec::new();
+
+    match self.chars.next() {
+      Some('$') => steps.push(PathStep::Root),
+      _ => return Err(self.syntax_error("path must start with '$'")),
+    }
+
+    while self.chars.peek().is_some() {
+      if self.eat('.') {
+        if self.eat('.') {
+          steps.push(self.parse_descendant()?);
+        } else if self.eat('*') {
+          steps.push(PathStep::Wildcard);
+        } else {
+          steps.push(PathStep::Child(self.parse_identifier()?));
+        }
+      } else if self.eat('[') {
+        steps.push(self.parse_bracket_step()?);
+      } else {
+        return Err(self.syntax_error("expected '.' or '['"));
+      }
+    }
+
+    Ok(steps)
+  }
+
+  fn parse_descendant(&mut self) -> Result<PathStep, JsonError> {
+    if self.eat('*') {
+      return Ok(PathStep::DescendantWildcard);
+    }
+
+    if self.eat('[') {
+      return match self.parse_bracket_step()? {
+        PathStep::Wildcard => Ok(PathStep::DescendantWildcard),
+        PathStep::Child(name) => Ok(PathStep::Descendant(name)),
+        _ => Err(self.syntax_error("'..[...]' only supports a name or '*'")),
+      };
+    }
+
+    Ok(PathStep::Descendant(self.parse_identifier()?))
+  }
+
+  fn parse_bracket_step(&mut self) -> Result<PathStep, JsonError> {
+    self.skip_whitespace();
+
+    if self.eat('*') {
+      self.skip_whitespace();
+      self.expect(']')?;
+      return Ok(PathStep::Wildcard);
+    }
+
+    if self.eat('?') {
+      self.expect('(')?;
+      let pred = self.parse_pred()?;
+      self.expect(')')?;
+      self.skip_whitespace();
+      self.expect(']')?;
+      return Ok(PathStep::Filter(pred));
+    }
+
+    let mut items = vec![self.parse_bracket_item()?];
+
+    self.skip_whitespace();
+
+    while self.eat(',') {
+      self.skip_whitespace();
+      items.push(self.parse_bracket_item()?);
+      self.skip_whitespace();
+    }
+
+    self.expect(']')?;
+
+    if items.len() == 1 {
+      Ok(items.pop().unwrap())
+    } else {
+      Ok(PathStep::Union(items))
+    }
+  }
+
+  fn parse_bracket_item(&mut self) -> Result<PathStep, JsonError> {
+    if self.chars.peek() == Some(&'\'') || self.chars.peek() == Some(&'"') {
+      return Ok(PathStep::Child(self.parse_quoted_string()?));
+    }
+
+    let start = self.parse_signed_int_opt()?;
+    self.skip_whitespace();
+
+    if self.eat(':') {
+      self.skip_whitespace();
+      let end = self.parse_signed_int_opt()?;
+      self.skip_whitespace();
+      let step = if self.eat(':') {
+        self.skip_whitespace();
+        self.parse_signed_int_opt()?
+      } else {
+        None
+      };
+
+      return Ok(PathStep::Slice { start, end, step });
+    }
+
+    match start {
+      Some(i) => Ok(PathStep::Index(i)),
+      None => Err(self.syntax_error("expected an index, slice or quoted name")),
+    }
+  }
+
+  fn parse_pred(&mut self) -> Result<Pred, JsonError> {
+    let mut lhs = self.parse_pred_comparison()?;
+
+    loop {
+      self.skip_whitespace();
+
+      if self.eat_str("&&") {
+        self.skip_whitespace();
+        lhs = Pred::And(Box::new(lhs), Box::new(self.parse_pred_comparison()?));
+      } else if self.eat_str("||") {
+        self.skip_whitespace();
+        lhs = Pred::Or(Box::new(lhs), Box::new(self.parse_pred_comparison()?));
+      } else {
+        break;
+      }
+    }
+
+    Ok(lhs)
+  }
+
+  fn parse_pred_comparison(&mut self) -> Result<Pred, JsonError> {
+    self.skip_whitespace();
+    let operand = self.parse_operand()?;
+    self.skip_whitespace();
+    let op = self.parse_compare_op()?;
+    self.skip_whitespace();
+    let literal = self.parse_literal()?;
+
+    Ok(Pred::Compare(operand, op, literal))
+  }
+
+  fn parse_operand(&mut self) -> Result<Operand, JsonError> {
+    self.expect('@')?;
+
+    if self.eat('.') {
+      Ok(Operand::Field(self.parse_identifier()?))
+    } else {
+      Ok(Operand::This)
+    }
+  }
+
+  fn parse_compare_op(&mut self) -> Result<CompareOp, JsonError> {
+    if self.eat_str("==") {
+      Ok(CompareOp::Eq)
+    } else if self.eat_str("!=") {
+      Ok(CompareOp::Ne)
+    } else if self.eat_str("<=") {
+      Ok(CompareOp::Le)
+    } else if self.eat_str(">=") {
+      Ok(CompareOp::Ge)
+    } else if self.eat('<') {
+      Ok(CompareOp::Lt)
+    } else if self.eat('>') {
+      Ok(CompareOp::Gt)
+    } else {
+      Err(self.syntax_error("expected a comparison operator"))
+    }
+  }
+
+  fn parse_literal(&mut self) -> Result<Literal, JsonError> {
+    match self.chars.peek() {
+      Some('\'') | Some('"') => Ok(Literal::String(self.parse_quoted_string()?)),
+      Some(c) if c.is_ascii_digit() || *c == '-' => {
+        let text = self.take_while(|c| c.is_ascii_digit() || c == '.' || c == '-' || c == '+' || c == 'e' || c == 'E');
+        text
+          .parse::<f64>()
+          .map(Literal::Number)
+          .map_err(|_| self.syntax_error("bad numeric literal"))
+      }
+      _ => {
+        if self.eat_str("true") {
+          Ok(Literal::Bool(true))
+        } else if self.eat_str("false") {
+          Ok(Literal::Bool(false))
+        } else {
+          Err(self.syntax_error("expected a literal"))
+        }
+      }
+    }
+  }
+
+  fn parse_identifier(&mut self) -> Result<String, JsonError> {
+    if self.eat('[') {
+      self.skip_whitespace();
+      let name = self.parse_quoted_string()?;
+      self.skip_whitespace();
+      self.expect(']')?;
+      return Ok(name);
+    }
+
+    let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '$');
+
+    if name.is_empty() {
+      return Err(self.syntax_error("expected a name"));
+    }
+
+    Ok(name)
+  }
+
+  fn parse_quoted_string(&mut self) -> Result<String, JsonError> {
+    let quote = match self.chars.next() {
+      Some(c @ '\'') | Some(c @ '"') => c,
+      _ => return Err(self.syntax_error("expected a quoted name")),
+    };
+    let mut result = String::new();
+
+    loop {
+      match self.chars.next() {
+        Some(c) if c == quote => break,
+        Some(c) => result.push(c),
+        None => return Err(self.syntax_error("unterminated quoted name")),
+      }
+    }
+
+    Ok(result)
+  }
+
+  fn parse_signed_int_opt(&mut self) -> Result<Option<i64>, JsonError> {
+    let text = self.take_while(|c| c.is_ascii_digit() || c == '-');
+
+    if text.is_empty() {
+      return Ok(None);
+    }
+
+    text
+      .parse::<i64>()
+      .map(Some)
+      .map_err(|_| self.syntax_error("bad integer"))
+  }
+
+  fn take_while(&mut self, mut pred: impl FnMut(char) -> bool) -> String {
+    let mut result = String::new();
+
+    while let Some(&c) = self.chars.peek() {
+      if pred(c) {
+        result.push(c);
+        self.chars.next();
+      } else {
+        break;
+      }
+    }
+
+    result
+  }
+
+  fn skip_whitespace(&mut self) {
+    self.take_while(|c| c.is_whitespace());
+  }
+
+  fn eat(&mut self, c: char) -> bool {
+    if self.chars.peek() == Some(&c) {
+      self.chars.next();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn eat_str(&mut self, s: &str) -> bool {
+    let mut clone = self.chars.clone();
+
+    for expected in s.chars() {
+      if clone.next() != Some(expected) {
+        return false;
+      }
+    }
+
+    self.chars = clone;
+    true
+  }
+
+  fn expect(&mut self, c: char) -> Result<(), JsonError> {
+    if self.eat(c) {
+      Ok(())
+    } else {
+      Err(self.syntax_error(&format!("expected '{}'", c)))
+    }
+  }
+
+  fn syntax_error(&self, message: &str) -> JsonError {
+    JsonError::Syntax(format!("invalid JSONPath expression: {}", message), None)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::parse;
+
+  #[test]
+  fn test_select_child() {
+    let tree = parse(r#"{servers: [{port: 8080}, {port: 8081}]}"#).unwrap();
+    let result = select(&tree, "$.servers[1].port").unwrap();
+
+    assert_eq!(result.len(), 1);
+    match result[0] {
+      JsonNode::Integer(8081, _) => (),
+      other => panic!("Unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_select_wildcard() {
+    let tree = parse("{a: 1, b: 2}").unwrap();
+    let result = select(&tree, "$.*").unwrap();
+
+    assert_eq!(result.len(), 2);
+  }
+
+  #[test]
+  fn test_select_descendant() {
+    let tree = parse("{a: {name: 1}, b: {c: {name: 2}}}").unwrap();
+    let result = select(&tree, "$..name").unwrap();
+
+    assert_eq!(result.len(), 2);
+  }
+
+  #[test]
+  fn test_select_descendant_top_level() {
+    let tree = parse("{foo: 1, bar: {foo: 2}}").unwrap();
+    let result = select(&tree, "$..foo").unwrap();
+
+    assert_eq!(result.len(), 2);
+    match (result[0], result[1]) {
+      (JsonNode::Integer(1, _), JsonNode::Integer(2, _)) => (),
+      other => panic!("Unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_select_negative_index() {
+    let tree = parse("[1, 2, 3]").unwrap();
+    let result = select(&tree, "$[-1]").unwrap();
+
+    assert_eq!(result.len(), 1);
+    match result[0] {
+      JsonNode::Integer(3, _) => (),
+      other => panic!("Unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_select_slice() {
+    let tree = parse("[1, 2, 3, 4, 5]").unwrap();
+    let result = select(&tree, "$[1:4]").unwrap();
+
+    assert_eq!(result.len(), 3);
+  }
+
+  #[test]
+  fn test_select_slice_start_below_negative_len() {
+    let tree = parse("[1, 2, 3, 4, 5]").unwrap();
+    let result = select(&tree, "$[-6:]").unwrap();
+
+    assert_eq!(result.len(), 5);
+    match (result[0], result[4]) {
+      (JsonNode::Integer(1, _), JsonNode::Integer(5, _)) => (),
+      other => panic!("Unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_select_slice_reverse() {
+    let tree = parse("[1, 2, 3, 4, 5]").unwrap();
+    let result = select(&tree, "$[::-1]").unwrap();
+
+    assert_eq!(result.len(), 5);
+    match (result[0], result[4]) {
+      (JsonNode::Integer(5, _), JsonNode::Integer(1, _)) => (),
+      other => panic!("Unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_select_filter() {
+    let tree = parse("[{price: 1}, {price: 25}]").unwrap();
+    let result = select(&tree, "$[?(@.price > 10)]").unwrap();
+
+    assert_eq!(result.len(), 1);
+  }
+
+  #[test]
+  fn test_select_owned() {
+    let tree = parse("{a: 1}").unwrap();
+    let result = select_owned(&tree, "$.a").unwrap();
+
+    assert_eq!(result.len(), 1);
+    match result[0] {
+      JsonNode::Integer(1, _) => (),
+      ref other => panic!("Unexpected result: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_select_bad_path() {
+    let tree = parse("{}").unwrap();
+
+    match select(&tree, "servers") {
+      Err(JsonError::Syntax(_, _)) => (),
+      _ => panic!("Unexpected result"),
+    }
+  }
+}
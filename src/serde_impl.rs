@@ -0,0 +1,581 @@
+//! Optional interop with the `serde` data model, enabled by the `serde`
+//! feature.
+
+use crate::{JsonError, JsonNode};
+use hashlink::LinkedHashMap;
+use serde::de::{self, DeserializeOwned, MapAccess, SeqAccess, Visitor};
+use serde::ser::{
+  self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+  SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+use std::fmt;
+
+impl ser::Error for JsonError {
+  fn custom<T: fmt::Display>(msg: T) -> Self {
+    JsonError::Syntax(msg.to_string(), None)
+  }
+}
+
+impl de::Error for JsonError {
+  fn custom<T: fmt::Display>(msg: T) -> Self {
+    JsonError::Syntax(msg.to_string(), None)
+  }
+}
+
+/// Serialize any [`Serialize`] value into a [`JsonNode`] tree with `None`
+/// locations, mirroring `serde_json::to_value`.
+pub fn to_node<T: ?Sized + Serialize>(value: &T) -> Result<JsonNode, JsonError> {
+  value.serialize(NodeSerializer)
+}
+
+/// Deserialize a [`JsonNode`] tree into any [`DeserializeOwned`] type,
+/// mirroring `serde_json::from_value`.
+pub fn from_node<T: DeserializeOwned>(node: &JsonNode) -> Result<T, JsonError> {
+  T::deserialize(NodeDeserializer(node.clone()))
+}
+
+impl Serialize for JsonNode {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: ser::Serializer,
+  {
+    match self {
+      JsonNode::Null(_) => serializer.serialize_unit(),
+      JsonNode::Bool(b, _) => serializer.serialize_bool(*b),
+      JsonNode::Integer(i, _) => serializer.serialize_i64(*i),
+      JsonNode::Float(f, _) => serializer.serialize_f64(*f),
+      JsonNode::Number(s, _) => serialize_number_text(s, serializer),
+      JsonNode::String(s, _) => serializer.serialize_str(s),
+      JsonNode::Array(a, _) => {
+        let mut seq = serializer.serialize_seq(Some(a.len()))?;
+
+        for item in a {
+          seq.serialize_element(item)?;
+        }
+
+        seq.end()
+      }
+      JsonNode::Object(o, _) => {
+        let mut map = serializer.serialize_map(Some(o.len()))?;
+
+        for (key, value) in o.iter() {
+          map.serialize_entry(key, value)?;
+        }
+
+        map.end()
+      }
+    }
+  }
+}
+
+impl<'de> de::Deserialize<'de> for JsonNode {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: de::Deserializer<'de>,
+  {
+    deserializer.deserialize_any(NodeVisitor)
+  }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+  type Value = JsonNode;
+
+  fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.write_str("a JSON5 value")
+  }
+
+  fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+    Ok(JsonNode::Bool(v, None))
+  }
+
+  fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+    Ok(JsonNode::Integer(v, None))
+  }
+
+  fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+  where
+    E: de::Error,
+  {
+    match i64::try_from(v) {
+      Ok(i) => Ok(JsonNode::Integer(i, None)),
+      Err(_) => Ok(JsonNode::Float(v as f64, None)),
+    }
+  }
+
+  fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+    Ok(JsonNode::Float(v, None))
+  }
+
+  fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+    Ok(JsonNode::String(v.to_string(), None))
+  }
+
+  fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+    Ok(JsonNode::String(v, None))
+  }
+
+  fn visit_unit<E>(self) -> Result<Self::Value, E> {
+    Ok(JsonNode::Null(None))
+  }
+
+  fn visit_none<E>(self) -> Result<Self::Value, E> {
+    Ok(JsonNode::Null(None))
+  }
+
+  fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+  where
+    D: de::Deserializer<'de>,
+  {
+    deserializer.deserialize_any(self)
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    let mut items = Vec::new();
+
+    while let Some(item) = seq.next_element()? {
+      items.push(item);
+    }
+
+    Ok(JsonNode::Array(items, None))
+  }
+
+  fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+  where
+    A: MapAccess<'de>,
+  {
+    let mut result = LinkedHashMap::new();
+
+    while let Some((key, value)) = map.next_entry::<String, JsonNode>()? {
+      result.insert(key, value);
+    }
+
+    Ok(JsonNode::Object(result, None))
+  }
+}
+
+/// Serialize a [`JsonNode::Number`]'s raw source text as the numeric value it
+/// represents, so a `serde_json::to_string` of a `parse_with`-preserved tree
+/// emits a bare number rather than a quoted string. Falls back to a string
+/// only for text that neither `as_i64` nor `as_f64` can parse, such as a
+/// hexadecimal literal too large for `i64`.
+fn serialize_number_text<S: ser::Serializer>(text: &str, serializer: S) -> Result<S::Ok, S::Error> {
+  let node = JsonNode::Number(text.to_string(), None);
+
+  match node.as_i64() {
+    Some(i) => serializer.serialize_i64(i),
+    None => match node.as_f64() {
+      Some(f) => serializer.serialize_f64(f),
+      None => serializer.serialize_str(text),
+    },
+  }
+}
+
+/// Mirror of [`serialize_number_text`] for the deserialize direction: visit a
+/// [`JsonNode::Number`]'s raw text as the numeric value it represents.
+fn visit_number_text<'de, V: Visitor<'de>>(text: String, visitor: V) -> Result<V::Value, JsonError> {
+  let node = JsonNode::Number(text, None);
+
+  match node.as_i64() {
+    Some(i) => visitor.visit_i64(i),
+    None => match node.as_f64() {
+      Some(f) => visitor.visit_f64(f),
+      None => visitor.visit_string(node.as_str().unwrap_or_default().to_string()),
+    },
+  }
+}
+
+struct NodeSerializer;
+
+struct SerializeArray(Vec<JsonNode>);
+
+impl SerializeSeq for SerializeArray {
+  type Ok = JsonNode;
+  type Error = JsonError;
+
+  fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+    self.0.push(to_node(value)?);
+    Ok(())
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Array(self.0, None))
+  }
+}
+
+impl SerializeTuple for SerializeArray {
+  type Ok = JsonNode;
+  type Error = JsonError;
+
+  fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+    SerializeSeq::serialize_element(self, value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    SerializeSeq::end(self)
+  }
+}
+
+impl SerializeTupleStruct for SerializeArray {
+  type Ok = JsonNode;
+  type Error = JsonError;
+
+  fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+    SerializeSeq::serialize_element(self, value)
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    SerializeSeq::end(self)
+  }
+}
+
+struct SerializeTupleVariantImpl {
+  name: &'static str,
+  items: Vec<JsonNode>,
+}
+
+impl SerializeTupleVariant for SerializeTupleVariantImpl {
+  type Ok = JsonNode;
+  type Error = JsonError;
+
+  fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+    self.items.push(to_node(value)?);
+    Ok(())
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    let mut map = LinkedHashMap::new();
+    map.insert(self.name.to_string(), JsonNode::Array(self.items, None));
+    Ok(JsonNode::Object(map, None))
+  }
+}
+
+struct SerializeObject {
+  map: LinkedHashMap<String, JsonNode>,
+  next_key: Option<String>,
+}
+
+impl SerializeMap for SerializeObject {
+  type Ok = JsonNode;
+  type Error = JsonError;
+
+  fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+    let node = to_node(key)?;
+
+    self.next_key = Some(match node {
+      JsonNode::String(s, _) => s,
+      other => return Err(JsonError::Syntax(format!("map keys must be strings, got {:?}", other), None)),
+    });
+
+    Ok(())
+  }
+
+  fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+    let key = self
+      .next_key
+      .take()
+      .ok_or_else(|| JsonError::Syntax("serialize_value called before serialize_key".to_string(), None))?;
+
+    self.map.insert(key, to_node(value)?);
+    Ok(())
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Object(self.map, None))
+  }
+}
+
+impl SerializeStruct for SerializeObject {
+  type Ok = JsonNode;
+  type Error = JsonError;
+
+  fn serialize_field<T: ?Sized + Serialize>(
+    &mut self,
+    key: &'static str,
+    value: &T,
+  ) -> Result<(), Self::Error> {
+    self.map.insert(key.to_string(), to_node(value)?);
+    Ok(())
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Object(self.map, None))
+  }
+}
+
+struct SerializeStructVariantImpl {
+  name: &'static str,
+  map: LinkedHashMap<String, JsonNode>,
+}
+
+impl SerializeStructVariant for SerializeStructVariantImpl {
+  type Ok = JsonNode;
+  type Error = JsonError;
+
+  fn serialize_field<T: ?Sized + Serialize>(
+    &mut self,
+    key: &'static str,
+    value: &T,
+  ) -> Result<(), Self::Error> {
+    self.map.insert(key.to_string(), to_node(value)?);
+    Ok(())
+  }
+
+  fn end(self) -> Result<Self::Ok, Self::Error> {
+    let mut outer = LinkedHashMap::new();
+    outer.insert(self.name.to_string(), JsonNode::Object(self.map, None));
+    Ok(JsonNode::Object(outer, None))
+  }
+}
+
+impl ser::Serializer for NodeSerializer {
+  type Ok = JsonNode;
+  type Error = JsonError;
+  type SerializeSeq = SerializeArray;
+  type SerializeTuple = SerializeArray;
+  type SerializeTupleStruct = SerializeArray;
+  type SerializeTupleVariant = SerializeTupleVariantImpl;
+  type SerializeMap = SerializeObject;
+  type SerializeStruct = SerializeObject;
+  type SerializeStructVariant = SerializeStructVariantImpl;
+
+  fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Bool(v, None))
+  }
+
+  fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+    self.serialize_i64(v as i64)
+  }
+
+  fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+    self.serialize_i64(v as i64)
+  }
+
+  fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+    self.serialize_i64(v as i64)
+  }
+
+  fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Integer(v, None))
+  }
+
+  fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+    self.serialize_i64(v as i64)
+  }
+
+  fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+    self.serialize_i64(v as i64)
+  }
+
+  fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+    self.serialize_i64(v as i64)
+  }
+
+  fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+    match i64::try_from(v) {
+      Ok(i) => Ok(JsonNode::Integer(i, None)),
+      Err(_) => self.serialize_f64(v as f64),
+    }
+  }
+
+  fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Float(v, None))
+  }
+
+  fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+    self.serialize_str(&v.to_string())
+  }
+
+  fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::String(v.to_string(), None))
+  }
+
+  fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+    let items = v.iter().map(|b| JsonNode::Integer(*b as i64, None)).collect();
+    Ok(JsonNode::Array(items, None))
+  }
+
+  fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Null(None))
+  }
+
+  fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+    value.serialize(self)
+  }
+
+  fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+    Ok(JsonNode::Null(None))
+  }
+
+  fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+    self.serialize_unit()
+  }
+
+  fn serialize_unit_variant(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+  ) -> Result<Self::Ok, Self::Error> {
+    self.serialize_str(variant)
+  }
+
+  fn serialize_newtype_struct<T: ?Sized + Serialize>(
+    self,
+    _name: &'static str,
+    value: &T,
+  ) -> Result<Self::Ok, Self::Error> {
+    value.serialize(self)
+  }
+
+  fn serialize_newtype_variant<T: ?Sized + Serialize>(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    value: &T,
+  ) -> Result<Self::Ok, Self::Error> {
+    let mut map = LinkedHashMap::new();
+    map.insert(variant.to_string(), to_node(value)?);
+    Ok(JsonNode::Object(map, None))
+  }
+
+  fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+    Ok(SerializeArray(Vec::with_capacity(len.unwrap_or(0))))
+  }
+
+  fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_struct(
+    self,
+    _name: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_variant(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+    Ok(SerializeTupleVariantImpl {
+      name: variant,
+      items: Vec::with_capacity(len),
+    })
+  }
+
+  fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+    Ok(SerializeObject {
+      map: LinkedHashMap::new(),
+      next_key: None,
+    })
+  }
+
+  fn serialize_struct(
+    self,
+    _name: &'static str,
+    _len: usize,
+  ) -> Result<Self::SerializeStruct, Self::Error> {
+    Ok(SerializeObject {
+      map: LinkedHashMap::new(),
+      next_key: None,
+    })
+  }
+
+  fn serialize_struct_variant(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    _len: usize,
+  ) -> Result<Self::SerializeStructVariant, Self::Error> {
+    Ok(SerializeStructVariantImpl {
+      name: variant,
+      map: LinkedHashMap::new(),
+    })
+  }
+}
+
+struct NodeDeserializer(JsonNode);
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer {
+  type Error = JsonError;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    match self.0 {
+      JsonNode::Null(_) => visitor.visit_unit(),
+      JsonNode::Bool(b, _) => visitor.visit_bool(b),
+      JsonNode::Integer(i, _) => visitor.visit_i64(i),
+      JsonNode::Float(f, _) => visitor.visit_f64(f),
+      JsonNode::Number(s, _) => visit_number_text(s, visitor),
+      JsonNode::String(s, _) => visitor.visit_string(s),
+      JsonNode::Array(a, _) => visitor.visit_seq(de::value::SeqDeserializer::new(
+        a.into_iter().map(NodeDeserializer),
+      )),
+      JsonNode::Object(o, _) => visitor.visit_map(de::value::MapDeserializer::new(
+        o.into_iter().map(|(k, v)| (k, NodeDeserializer(v))),
+      )),
+    }
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    match self.0 {
+      JsonNode::Null(_) => visitor.visit_none(),
+      _ => visitor.visit_some(self),
+    }
+  }
+
+  serde::forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+    map struct enum identifier ignored_any
+  }
+}
+
+impl<'de> de::IntoDeserializer<'de, JsonError> for NodeDeserializer {
+  type Deserializer = Self;
+
+  fn into_deserializer(self) -> Self::Deserializer {
+    self
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_to_node_from_node_struct() {
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Point {
+      x: i32,
+      y: i32,
+    }
+
+    let point = Point { x: 1, y: 2 };
+    let node = to_node(&point).unwrap();
+
+    assert_eq!(from_node::<Point>(&node).unwrap(), point);
+  }
+
+  #[test]
+  fn test_to_node_from_node_number() {
+    let node = JsonNode::Number("1.10".to_string(), None);
+
+    assert_eq!(from_node::<f64>(&node).unwrap(), 1.10);
+  }
+}
+